@@ -0,0 +1,168 @@
+//! Shared helpers used by the generated WASI integration test suite.
+//!
+//! Everything in here is invoked from the files under `tests/generated/`,
+//! which are produced by `cargo xtask generate tests` and should not be
+//! edited by hand -- put shared logic here instead of duplicating it in
+//! the generator's codegen templates.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Output;
+
+use anyhow::{bail, Context, Result};
+use tempfile::TempDir;
+use walkdir::WalkDir;
+use xshell::{cmd, Shell};
+
+/// Creates a uniquely-suffixed temp directory for `name`
+/// (`<name><random-bytes>` under the system temp dir), so that
+/// concurrently-running tests for different fixtures -- or repeated runs
+/// of the same fixture -- never share a directory. The directory and its
+/// contents are removed when the returned `TempDir` is dropped.
+pub fn tempdir(name: &str) -> Result<TempDir> {
+    tempfile::Builder::new()
+        .prefix(name)
+        .rand_bytes(8)
+        .tempdir()
+        .with_context(|| format!("failed to create temp directory for `{name}`"))
+}
+
+/// Compiles the guest fixture `name` (the `tests/wasi-tests/<name>.rs`
+/// source) to a wasm component inside `tempdir`, returning the path to
+/// the resulting `.wasm` file.
+pub fn compile(sh: &Shell, tempdir: &TempDir, name: &str) -> Result<PathBuf> {
+    let out_dir = tempdir.path();
+    let src = Path::new("tests/wasi-tests").join(format!("{name}.rs"));
+    let wasm_file = out_dir.join(format!("{name}.wasm"));
+    cmd!(
+        sh,
+        "rustc --edition 2021 --target wasm32-wasi -O {src} -o {wasm_file}"
+    )
+    .run()
+    .with_context(|| format!("failed to compile fixture `{name}`"))?;
+    Ok(wasm_file)
+}
+
+/// An expected value for a captured stream: either an exact string match
+/// (trailing newline-insensitive) or a regex. Parsed out of a fixture's
+/// `//@` directive header by `xtask`, and re-embedded verbatim into the
+/// generated test, which calls [`Expect::check`] against the captured
+/// output.
+#[derive(Debug, Clone)]
+pub enum Expect {
+    Exact(String),
+    Regex(String),
+}
+
+impl Expect {
+    /// Checks `actual` (the captured stdout/stderr text) against this
+    /// expectation, bailing with a diff-ish message on mismatch.
+    pub fn check(&self, which: &str, actual: &str) -> Result<()> {
+        let matched = match self {
+            Expect::Exact(expected) => actual.trim_end_matches('\n') == expected.trim_end_matches('\n'),
+            Expect::Regex(pattern) => regex::Regex::new(pattern)
+                .with_context(|| format!("invalid {which} regex: `{pattern}`"))?
+                .is_match(actual),
+        };
+        if !matched {
+            bail!(
+                "unexpected {which}\n--- expected ---\n{:?}\n--- actual ---\n{actual}",
+                self
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Asserts that `output`'s exit code matches `expected`.
+pub fn check_exit(output: &Output, expected: i32) -> Result<()> {
+    let code = output.status.code();
+    if code != Some(expected) {
+        bail!("expected exit code {expected}, got {code:?}");
+    }
+    Ok(())
+}
+
+/// Recursively copies `tests/fixtures/<name>/input/` into `dest`,
+/// mirroring its directory structure, so the component can see the
+/// files through its preopened run directory. No-op if the fixture has
+/// no `input/` tree (callers only invoke this when `xtask` determined
+/// one exists at generation time).
+pub fn seed_input(name: &str, dest: &Path) -> Result<()> {
+    let input_dir = Path::new("tests/fixtures").join(name).join("input");
+    if !input_dir.is_dir() {
+        return Ok(());
+    }
+    for entry in WalkDir::new(&input_dir) {
+        let entry = entry.with_context(|| format!("walking `{}`", input_dir.display()))?;
+        let rel = entry
+            .path()
+            .strip_prefix(&input_dir)
+            .expect("WalkDir yields paths under input_dir");
+        let target = dest.join(rel);
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&target)?;
+        } else {
+            std::fs::copy(entry.path(), &target)
+                .with_context(|| format!("copying `{}` to `{}`", entry.path().display(), target.display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Recursively compares `dir` against `tests/fixtures/<name>/expected/` in
+/// both directions, bailing out with the first path that's missing,
+/// unexpected, or whose contents differ. No-op if the fixture has no
+/// `expected/` tree.
+pub fn assert_output(name: &str, dir: &Path) -> Result<()> {
+    let expected_dir = Path::new("tests/fixtures").join(name).join("expected");
+    if !expected_dir.is_dir() {
+        return Ok(());
+    }
+    let mut expected_files = HashSet::new();
+
+    for entry in WalkDir::new(&expected_dir) {
+        let entry = entry.with_context(|| format!("walking `{}`", expected_dir.display()))?;
+        if entry.file_type().is_dir() {
+            continue;
+        }
+        let rel = entry
+            .path()
+            .strip_prefix(&expected_dir)
+            .expect("WalkDir yields paths under expected_dir")
+            .to_path_buf();
+        let actual_path = dir.join(&rel);
+        let expected_bytes = std::fs::read(entry.path())
+            .with_context(|| format!("reading `{}`", entry.path().display()))?;
+        let actual_bytes = std::fs::read(&actual_path)
+            .with_context(|| format!("expected output file `{}` is missing", actual_path.display()))?;
+        if actual_bytes != expected_bytes {
+            bail!(
+                "run directory contents differ from `{}` at `{}`",
+                expected_dir.display(),
+                rel.display()
+            );
+        }
+        expected_files.insert(rel);
+    }
+
+    for entry in WalkDir::new(dir) {
+        let entry = entry.with_context(|| format!("walking `{}`", dir.display()))?;
+        if entry.file_type().is_dir() {
+            continue;
+        }
+        let rel = entry
+            .path()
+            .strip_prefix(dir)
+            .expect("WalkDir yields paths under dir");
+        if !expected_files.contains(rel) {
+            bail!(
+                "run directory has unexpected file `{}` not present in `{}`",
+                rel.display(),
+                expected_dir.display()
+            );
+        }
+    }
+
+    Ok(())
+}
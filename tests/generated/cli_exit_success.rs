@@ -1,17 +1,18 @@
 //! This file has been auto-generated, please do not modify manually
 //! To regenerate this file re-run `cargo xtask generate tests` from the project root
 
-use std::fs;
-use tempdir::TempDir;
 use xshell::{cmd, Shell};
 
 #[test]
 fn cli_exit_success() -> anyhow::Result<()> {
     let sh = Shell::new()?;
     let file_name = "cli_exit_success";
-    let tempdir = TempDir::new("{file_name}")?;
-    let wasi_file = test_utils::compile(&sh, &tempdir, &file_name)?;
-    let _ = fs::remove_dir_all("./tests/rundir/cli_exit_success");
-    cmd!(sh, "./src/jco.js run  --jco-dir ./tests/rundir/cli_exit_success --jco-import ./tests/virtualenvs/base.js {wasi_file} hello this '' 'is an argument' 'with 🚩 emoji'").run()?;
+    let tempdir = test_utils::tempdir(file_name)?;
+    let wasi_file = test_utils::compile(&sh, &tempdir, file_name)?;
+    let rundir = test_utils::tempdir(file_name)?;
+    let rundir_path = rundir.path();
+    let cmd = cmd!(sh, "./src/jco.js run  --jco-dir {rundir_path} --jco-import ./tests/virtualenvs/base.js {wasi_file} hello this '' 'is an argument' 'with 🚩 emoji'");
+    let output = cmd.ignore_status().output()?;
+    test_utils::check_exit(&output, 0)?;
     Ok(())
 }
@@ -0,0 +1,6 @@
+//@ exit: 0
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    println!("{}", args.join(" "));
+}
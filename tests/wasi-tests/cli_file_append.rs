@@ -0,0 +1,14 @@
+//@ import: bar-jabberwock.js
+
+use std::fs::OpenOptions;
+use std::io::Write;
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open("/log.txt")
+        .expect("failed to open /log.txt");
+    writeln!(file, "{}", args.join(" ")).expect("failed to append to /log.txt");
+}
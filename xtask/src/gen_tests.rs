@@ -0,0 +1,202 @@
+//! Generates `tests/generated/<name>.rs` from the guest fixtures under
+//! `tests/wasi-tests/`.
+//!
+//! Each fixture may carry a leading `//@` directive header (in the style
+//! of `rustc`'s ui test suite / the `lang-tester` crate) describing what
+//! the generated test should assert about the run:
+//!
+//! ```text
+//! //@ args: hello world
+//! //@ stdout: Hello, world!
+//! //@ stderr: regex:^warning:.*$
+//! //@ exit: 0
+//! ```
+//!
+//! `args` overrides the default argument list passed to `jco.js run`.
+//! `stdout` and `stderr` are matched exactly (ignoring trailing
+//! newlines) unless prefixed with
+//! `regex:`, in which case the remainder is compiled as a regex and
+//! matched against the captured stream. `exit` defaults to `0`. A
+//! fixture with no directives at all gets the old "the run must not
+//! error" test. `mapdir` adds a `--mapdir <guest-path>::<host-path>`
+//! flag to the invocation and may be repeated. `runtool` adds a
+//! `--runtool <cmd>` flag to run the component under an external
+//! launcher instead of the embedded runtime.
+//!
+//! A fixture named `<name>` can also carry filesystem fixtures under
+//! `tests/fixtures/<name>/`: an `input/` tree is mirrored into the run
+//! directory before the component runs (so it can read preopened
+//! files), and an `expected/` tree is diffed against the run directory
+//! afterwards (so writes made by the component can be checked). Either,
+//! both, or neither may be present; the generator only emits the
+//! corresponding seed/assert calls for the ones that exist.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+const WASI_TESTS_DIR: &str = "tests/wasi-tests";
+const GENERATED_DIR: &str = "tests/generated";
+const FIXTURES_DIR: &str = "tests/fixtures";
+const DEFAULT_ARGS: &str = "hello this '' 'is an argument' 'with 🚩 emoji'";
+
+#[derive(Debug, Default)]
+struct Directives {
+    /// Which `tests/virtualenvs/*.js` import shim to pass to `--jco-import`.
+    import: Option<String>,
+    args: Option<String>,
+    stdout: Option<Expect>,
+    stderr: Option<Expect>,
+    exit: Option<i32>,
+    /// `--mapdir <guest-path>::<host-path>` flags, in directive order.
+    mapdir: Vec<String>,
+    /// `--runtool <cmd>`, the external launcher to run the component under.
+    runtool: Option<String>,
+}
+
+#[derive(Debug)]
+enum Expect {
+    Exact(String),
+    Regex(String),
+}
+
+pub fn generate() -> Result<()> {
+    fs::create_dir_all(GENERATED_DIR)?;
+    for entry in fs::read_dir(WASI_TESTS_DIR)
+        .with_context(|| format!("reading `{WASI_TESTS_DIR}`"))?
+    {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("rs") {
+            continue;
+        }
+        let name = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .with_context(|| format!("non-utf8 fixture name: {}", path.display()))?
+            .to_string();
+        let source = fs::read_to_string(&path)
+            .with_context(|| format!("reading fixture `{}`", path.display()))?;
+        let directives = parse_directives(&source)?;
+        let fixture_dir = Path::new(FIXTURES_DIR).join(&name);
+        let has_input = fixture_dir.join("input").is_dir();
+        let has_expected = fixture_dir.join("expected").is_dir();
+        let code = render_test(&name, &directives, has_input, has_expected);
+        fs::write(Path::new(GENERATED_DIR).join(format!("{name}.rs")), code)?;
+    }
+    Ok(())
+}
+
+/// Parses the leading `//@` header of a fixture. Lines stop being
+/// considered part of the header at the first non-`//@` line.
+fn parse_directives(source: &str) -> Result<Directives> {
+    let mut directives = Directives::default();
+    for line in source.lines() {
+        let Some(rest) = line.trim_start().strip_prefix("//@") else {
+            break;
+        };
+        let rest = rest.trim();
+        let Some((key, value)) = rest.split_once(':') else {
+            continue;
+        };
+        let value = value.trim().to_string();
+        match key.trim() {
+            "import" => directives.import = Some(value),
+            "args" => directives.args = Some(value),
+            "stdout" => directives.stdout = Some(parse_expect(value)),
+            "stderr" => directives.stderr = Some(parse_expect(value)),
+            "exit" => directives.exit = Some(value.parse().context("`//@ exit:` must be an integer")?),
+            "mapdir" => directives.mapdir.push(value),
+            "runtool" => directives.runtool = Some(value),
+            other => anyhow::bail!("unknown directive `//@ {other}:`"),
+        }
+    }
+    Ok(directives)
+}
+
+fn parse_expect(value: String) -> Expect {
+    match value.strip_prefix("regex:") {
+        Some(pattern) => Expect::Regex(pattern.to_string()),
+        None => Expect::Exact(value),
+    }
+}
+
+impl Expect {
+    /// Renders this expectation as the `test_utils::Expect` constructor
+    /// call to embed in the generated test source.
+    fn to_rust(&self) -> String {
+        match self {
+            Expect::Exact(s) => format!("test_utils::Expect::Exact({s:?}.to_string())"),
+            Expect::Regex(s) => format!("test_utils::Expect::Regex({s:?}.to_string())"),
+        }
+    }
+}
+
+fn render_test(name: &str, directives: &Directives, has_input: bool, has_expected: bool) -> String {
+    let args = directives.args.as_deref().unwrap_or(DEFAULT_ARGS);
+    let import = directives.import.as_deref().unwrap_or("base.js");
+    let has_expectations =
+        directives.stdout.is_some() || directives.stderr.is_some() || directives.exit.is_some();
+    let mapdir_flags: String = directives
+        .mapdir
+        .iter()
+        .map(|mapping| format!(" --mapdir {mapping}"))
+        .collect();
+    let runtool_flags = match &directives.runtool {
+        Some(runtool) => format!(" --runtool {runtool}"),
+        None => String::new(),
+    };
+
+    let mut out = String::new();
+    out.push_str("//! This file has been auto-generated, please do not modify manually\n");
+    out.push_str("//! To regenerate this file re-run `cargo xtask generate tests` from the project root\n\n");
+    out.push_str("use xshell::{cmd, Shell};\n\n");
+    out.push_str("#[test]\n");
+    out.push_str(&format!("fn {name}() -> anyhow::Result<()> {{\n"));
+    out.push_str("    let sh = Shell::new()?;\n");
+    out.push_str(&format!("    let file_name = \"{name}\";\n"));
+    out.push_str("    let tempdir = test_utils::tempdir(file_name)?;\n");
+    out.push_str("    let wasi_file = test_utils::compile(&sh, &tempdir, file_name)?;\n");
+    out.push_str("    let rundir = test_utils::tempdir(file_name)?;\n");
+    out.push_str("    let rundir_path = rundir.path();\n");
+    if has_input {
+        out.push_str("    test_utils::seed_input(file_name, rundir_path)?;\n");
+    }
+
+    if has_expectations {
+        out.push_str(&format!(
+            "    let cmd = cmd!(sh, \"./src/jco.js run  --jco-dir {{rundir_path}} --jco-import ./tests/virtualenvs/{import}{mapdir_flags}{runtool_flags} {{wasi_file}} {args}\");\n"
+        ));
+        out.push_str("    let output = cmd.ignore_status().output()?;\n");
+        if let Some(expect) = &directives.stdout {
+            out.push_str("    let stdout = String::from_utf8_lossy(&output.stdout);\n");
+            out.push_str(&format!(
+                "    {}.check(\"stdout\", &stdout)?;\n",
+                expect.to_rust()
+            ));
+        }
+        if let Some(expect) = &directives.stderr {
+            out.push_str("    let stderr = String::from_utf8_lossy(&output.stderr);\n");
+            out.push_str(&format!(
+                "    {}.check(\"stderr\", &stderr)?;\n",
+                expect.to_rust()
+            ));
+        }
+        out.push_str(&format!(
+            "    test_utils::check_exit(&output, {})?;\n",
+            directives.exit.unwrap_or(0)
+        ));
+    } else {
+        out.push_str(&format!(
+            "    cmd!(sh, \"./src/jco.js run  --jco-dir {{rundir_path}} --jco-import ./tests/virtualenvs/{import}{mapdir_flags}{runtool_flags} {{wasi_file}} {args}\").run()?;\n"
+        ));
+    }
+
+    if has_expected {
+        out.push_str("    test_utils::assert_output(file_name, rundir_path)?;\n");
+    }
+
+    out.push_str("    Ok(())\n");
+    out.push_str("}\n");
+    out
+}
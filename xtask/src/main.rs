@@ -0,0 +1,13 @@
+//! Project automation, invoked as `cargo xtask <command>`.
+
+mod gen_tests;
+
+use anyhow::{bail, Result};
+
+fn main() -> Result<()> {
+    let mut args = std::env::args().skip(1);
+    match (args.next().as_deref(), args.next().as_deref()) {
+        (Some("generate"), Some("tests")) => gen_tests::generate(),
+        _ => bail!("usage: cargo xtask generate tests"),
+    }
+}